@@ -1,12 +1,14 @@
 use std::{
+    collections::HashSet,
     error::Error,
     fmt::Display,
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, Cursor, Write},
     num::ParseIntError,
     str::FromStr,
 };
 
+use num_complex::Complex;
 use num_traits::Num;
 
 /// List all the possibles errors that could occurs.
@@ -22,16 +24,64 @@ pub enum MtxError {
     UnsupportedLayout(String),
     InvalidNum(String),
     InvalidCoordinate(ParseIntError),
+    /// A coordinate was `0`; matrix market indices are 1-based, so there is
+    /// no 0-based index to convert it to.
+    ZeroCoordinate,
+    NonZeroSkewDiagonal,
+    /// A validated sparse entry that failed a check (out of bounds, duplicate,
+    /// or, for symmetric data, an off-diagonal pair listed on both triangles),
+    /// locating the offending 1-based data line and its raw text.
+    InvalidLine {
+        line: usize,
+        text: String,
+        reason: String,
+    },
+}
+
+/// The numeric kind of the data lines, as declared by the 4th field of the banner.
+///
+/// `Pattern` matrices carry only coordinates (no value), and `Complex` matrices
+/// carry two values (real and imaginary parts) per data line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumKind {
+    Real,
+    Integer,
+    Complex,
+    Pattern,
+}
+
+impl FromStr for NumKind {
+    type Err = MtxError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_end() {
+            "real" => Ok(NumKind::Real),
+            "integer" => Ok(NumKind::Integer),
+            "complex" => Ok(NumKind::Complex),
+            "pattern" => Ok(NumKind::Pattern),
+            other => Err(MtxError::UnsupportedNumType(other.to_owned())),
+        }
+    }
+}
+
+/// The fully parsed banner line (`%%MatrixMarket matrix <layout> <num_kind> <sym>`),
+/// so that callers can branch on what was actually declared in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeCode {
+    pub sparse: bool,
+    pub num_kind: NumKind,
+    pub sym: SymInfo,
 }
 
 impl Display for MtxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use MtxError::*;
-        let msg = match self {
-            IoError(_) => "IO error occurs when manipulate mtx file",
-            _ => "Invalid mtx text format",
-        };
-        write!(f, "{msg}")
+        match self {
+            IoError(_) => write!(f, "IO error occurs when manipulate mtx file"),
+            InvalidLine { line, text, reason } => {
+                write!(f, "invalid mtx data at line {line} (\"{text}\"): {reason}")
+            }
+            _ => write!(f, "Invalid mtx text format"),
+        }
     }
 }
 
@@ -59,13 +109,12 @@ impl Error for MtxError {
 }
 
 /// Symmetry information in the matrix market banner.
-/// Currently we dont support all of the info available in the format.
-/// Because we dont handle complex numbers.
-/// Feel free to contribute and add the missing support for those numbers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SymInfo {
     General,
     Symmetric,
+    SkewSymmetric,
+    Hermitian,
 }
 
 impl FromStr for SymInfo {
@@ -74,19 +123,39 @@ impl FromStr for SymInfo {
         match s.trim_end() {
             "general" => Ok(SymInfo::General),
             "symmetric" => Ok(SymInfo::Symmetric),
+            "skew-symmetric" => Ok(SymInfo::SkewSymmetric),
+            "hermitian" => Ok(SymInfo::Hermitian),
             other => Err(MtxError::UnsupportedSym(other.to_owned())),
         }
     }
 }
 
-/// The main enum of this crate, corresponding to the 2 kind of usage of mtx files.
-/// Both contains a first line with dimensions.
+impl Display for SymInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SymInfo::General => "general",
+            SymInfo::Symmetric => "symmetric",
+            SymInfo::SkewSymmetric => "skew-symmetric",
+            SymInfo::Hermitian => "hermitian",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The main enum of this crate, corresponding to the kinds of usage of mtx files.
+/// All variants contain a first field with dimensions.
 /// Dense is a list of numbers.
 /// Sparse is a list of coordinates and values.
+/// The `Complex` variants mirror `Dense`/`Sparse` for `complex` banners, storing
+/// `Complex<T>` instead of `T`. `SparsePattern` mirrors `Sparse` for `pattern`
+/// banners, where data lines carry only coordinates and no value.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MtxData<T: Num, const NDIM: usize = 2> {
     Dense([usize; NDIM], Vec<T>, SymInfo),
     Sparse([usize; NDIM], Vec<[usize; NDIM]>, Vec<T>, SymInfo),
+    DenseComplex([usize; NDIM], Vec<Complex<T>>, SymInfo),
+    SparseComplex([usize; NDIM], Vec<[usize; NDIM]>, Vec<Complex<T>>, SymInfo),
+    SparsePattern([usize; NDIM], Vec<[usize; NDIM]>, SymInfo),
 }
 
 impl<T: Num, const NDIM: usize> MtxData<T, NDIM> {
@@ -124,27 +193,377 @@ impl<T: Num, const NDIM: usize> MtxData<T, NDIM> {
     /// It could fail for many reasons but for example:
     /// - File doesn't match the matrix market format.
     /// - an IO error (file not found etc.)
-    pub fn from_file(path: &str) -> Result<Self, MtxError> {
+    pub fn from_file(path: &str) -> Result<Self, MtxError>
+    where
+        T: Clone,
+    {
         let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
+        let reader = BufReader::new(file);
+        Self::from_reader(reader)
+    }
+
+    /// Build a `MtxData` from anything implementing [`BufRead`], such as a
+    /// `BufReader` wrapping a `TcpStream` or a decompressing reader.
+    ///
+    /// # Example
+    /// ```rust
+    /// use matrix_market_rs::{MtxData, SymInfo, MtxError};
+    /// use std::io::BufReader;
+    ///
+    /// fn main() -> Result<(), MtxError> {
+    ///     let mtx_content = r#"
+    ///     %%MatrixMarket matrix coordinate integer symmetric
+    ///     2 2 2
+    ///     1 1 3
+    ///     2 2 4
+    ///     "#;
+    ///
+    ///     let reader = BufReader::new(mtx_content.trim().as_bytes());
+    ///     let sparse: MtxData<i32> = MtxData::from_reader(reader)?;
+    ///     assert!(matches!(sparse, MtxData::Sparse(..)));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It could fail for many reasons but for example:
+    /// - The data doesn't match the matrix market format.
+    /// - an IO error on the reader.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Self, MtxError>
+    where
+        T: Clone,
+    {
         let mut line = String::new();
-        let (is_sparse, sym) = parse_banner(&mut reader, &mut line)?;
+        let typecode = parse_banner(&mut reader, &mut line)?;
+        let sym = typecode.sym;
         skip_comments(&mut reader, &mut line)?;
         let (dims, nnz) = parse_sizes(&mut line)?;
-        if is_sparse {
-            let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
-            let (indices, values) = parse_sparse_coo(&mut reader, &mut line, nnz)?;
-            Ok(MtxData::Sparse(dims, indices, values, sym))
-        } else {
-            let capacity = dims.iter().product();
-            let values = parse_dense_vec(&mut reader, &mut line, capacity)?;
-            Ok(MtxData::Dense(dims, values, sym))
+        match (typecode.sparse, typecode.num_kind) {
+            (true, NumKind::Real | NumKind::Integer) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let (indices, values) = parse_sparse_coo(&mut reader, &mut line, nnz)?;
+                Ok(MtxData::Sparse(dims, indices, values, sym))
+            }
+            (false, NumKind::Real | NumKind::Integer) => {
+                let values = parse_dense_vec(&mut reader, &mut line, dims, sym)?;
+                Ok(MtxData::Dense(dims, values, sym))
+            }
+            (true, NumKind::Complex) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let (indices, values) = parse_sparse_coo_complex(&mut reader, &mut line, nnz)?;
+                Ok(MtxData::SparseComplex(dims, indices, values, sym))
+            }
+            (false, NumKind::Complex) => {
+                let values = parse_dense_complex_vec(&mut reader, &mut line, dims, sym)?;
+                Ok(MtxData::DenseComplex(dims, values, sym))
+            }
+            (true, NumKind::Pattern) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let indices = parse_sparse_coo_pattern(&mut reader, &mut line, nnz)?;
+                Ok(MtxData::SparsePattern(dims, indices, sym))
+            }
+            (false, NumKind::Pattern) => {
+                Err(MtxError::UnsupportedLayout("pattern array".to_owned()))
+            }
+        }
+    }
+
+    /// Like [`MtxData::from_file`], but validates every sparse entry: each
+    /// coordinate must be within `[dims]`, no coordinate may repeat, and for
+    /// symmetric/skew-symmetric/hermitian data, no off-diagonal pair may be
+    /// listed on both triangles.
+    /// On failure, the [`MtxError::InvalidLine`] carries the offending
+    /// 1-based data line and its raw text.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`MtxData::from_file`], plus [`MtxError::InvalidLine`] for an
+    /// invalid sparse entry.
+    pub fn from_file_validated(path: &str) -> Result<Self, MtxError>
+    where
+        T: Clone,
+    {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        Self::from_reader_validated(reader)
+    }
+
+    /// Like [`MtxData::from_reader`], but validates every sparse entry. See
+    /// [`MtxData::from_file_validated`] for the checks performed.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`MtxData::from_reader`], plus [`MtxError::InvalidLine`] for an
+    /// invalid sparse entry.
+    pub fn from_reader_validated<R: BufRead>(mut reader: R) -> Result<Self, MtxError>
+    where
+        T: Clone,
+    {
+        let mut line = String::new();
+        let typecode = parse_banner(&mut reader, &mut line)?;
+        let sym = typecode.sym;
+        skip_comments(&mut reader, &mut line)?;
+        let (dims, nnz) = parse_sizes(&mut line)?;
+        match (typecode.sparse, typecode.num_kind) {
+            (true, NumKind::Real | NumKind::Integer) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let (indices, values) =
+                    parse_sparse_coo_validated(&mut reader, &mut line, nnz, dims, sym)?;
+                Ok(MtxData::Sparse(dims, indices, values, sym))
+            }
+            (false, NumKind::Real | NumKind::Integer) => {
+                let values = parse_dense_vec(&mut reader, &mut line, dims, sym)?;
+                Ok(MtxData::Dense(dims, values, sym))
+            }
+            (true, NumKind::Complex) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let (indices, values) =
+                    parse_sparse_coo_complex_validated(&mut reader, &mut line, nnz, dims, sym)?;
+                Ok(MtxData::SparseComplex(dims, indices, values, sym))
+            }
+            (false, NumKind::Complex) => {
+                let values = parse_dense_complex_vec(&mut reader, &mut line, dims, sym)?;
+                Ok(MtxData::DenseComplex(dims, values, sym))
+            }
+            (true, NumKind::Pattern) => {
+                let nnz = nnz.ok_or(MtxError::EarlySizesHeaderEnd)?;
+                let indices =
+                    parse_sparse_coo_pattern_validated(&mut reader, &mut line, nnz, dims, sym)?;
+                Ok(MtxData::SparsePattern(dims, indices, sym))
+            }
+            (false, NumKind::Pattern) => {
+                Err(MtxError::UnsupportedLayout("pattern array".to_owned()))
+            }
+        }
+    }
+
+    /// Write this `MtxData` to a matrix market (usually .mtx) file.
+    ///
+    /// # Example
+    /// ```rust
+    /// use matrix_market_rs::{MtxData, SymInfo, MtxError};
+    ///
+    /// fn main() -> Result<(), MtxError> {
+    ///     let shape = [2, 2];
+    ///     let indices = vec![[0, 0], [1, 1]];
+    ///     let nonzeros = vec![3, 4];
+    ///     let sparse = MtxData::Sparse(shape, indices, nonzeros, SymInfo::Symmetric);
+    ///     sparse.to_file("sparse2x2.mtx")?;
+    ///
+    ///     let reloaded: MtxData<i32> = MtxData::from_file("sparse2x2.mtx")?;
+    ///     assert_eq!(sparse, reloaded);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It could fail because of an IO error (e.g. permission denied, disk full).
+    pub fn to_file(&self, path: &str) -> Result<(), MtxError>
+    where
+        T: Display,
+    {
+        let mut file = File::create(path)?;
+        self.write(&mut file)
+    }
+
+    /// Write this `MtxData` to any [`Write`]r, following the matrix market format.
+    ///
+    /// This is the counterpart of [`MtxData::from_reader`], emitting the banner line,
+    /// the size header and the data lines, converting the 0-based internal indices
+    /// back to matrix market's 1-based coordinates.
+    ///
+    /// `Dense` and `Sparse` always declare their banner type as `real`, since
+    /// [`NumKind::Real`] and [`NumKind::Integer`] both parse into the same
+    /// variant and the distinction isn't kept around; a file originally
+    /// declared `integer` round-trips its values but comes back out as `real`.
+    ///
+    /// # Errors
+    ///
+    /// It could fail because of an IO error on the writer.
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<(), MtxError>
+    where
+        T: Display,
+    {
+        match self {
+            MtxData::Dense(dims, values, sym) => {
+                writeln!(w, "%%MatrixMarket matrix array real {sym}")?;
+                let dims_str: Vec<String> = dims.iter().map(usize::to_string).collect();
+                writeln!(w, "{}", dims_str.join(" "))?;
+                for val in dense_stored_values(values, *dims, *sym) {
+                    writeln!(w, "{val}")?;
+                }
+            }
+            MtxData::Sparse(dims, indices, values, sym) => {
+                writeln!(w, "%%MatrixMarket matrix coordinate real {sym}")?;
+                let dims_str: Vec<String> = dims.iter().map(usize::to_string).collect();
+                writeln!(w, "{} {}", dims_str.join(" "), values.len())?;
+                for (coords, val) in indices.iter().zip(values) {
+                    let coords_str: Vec<String> =
+                        coords.iter().map(|c| (c + 1).to_string()).collect();
+                    writeln!(w, "{} {val}", coords_str.join(" "))?;
+                }
+            }
+            MtxData::DenseComplex(dims, values, sym) => {
+                writeln!(w, "%%MatrixMarket matrix array complex {sym}")?;
+                let dims_str: Vec<String> = dims.iter().map(usize::to_string).collect();
+                writeln!(w, "{}", dims_str.join(" "))?;
+                for val in dense_stored_values(values, *dims, *sym) {
+                    writeln!(w, "{} {}", val.re, val.im)?;
+                }
+            }
+            MtxData::SparseComplex(dims, indices, values, sym) => {
+                writeln!(w, "%%MatrixMarket matrix coordinate complex {sym}")?;
+                let dims_str: Vec<String> = dims.iter().map(usize::to_string).collect();
+                writeln!(w, "{} {}", dims_str.join(" "), values.len())?;
+                for (coords, val) in indices.iter().zip(values) {
+                    let coords_str: Vec<String> =
+                        coords.iter().map(|c| (c + 1).to_string()).collect();
+                    writeln!(w, "{} {} {}", coords_str.join(" "), val.re, val.im)?;
+                }
+            }
+            MtxData::SparsePattern(dims, indices, sym) => {
+                writeln!(w, "%%MatrixMarket matrix coordinate pattern {sym}")?;
+                let dims_str: Vec<String> = dims.iter().map(usize::to_string).collect();
+                writeln!(w, "{} {}", dims_str.join(" "), indices.len())?;
+                for coords in indices {
+                    let coords_str: Vec<String> =
+                        coords.iter().map(|c| (c + 1).to_string()).collect();
+                    writeln!(w, "{}", coords_str.join(" "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Materialize the full matrix from a symmetric/skew-symmetric/hermitian
+    /// sparse or pattern `MtxData`, emitting the mirror `(j, i)` entry for every
+    /// stored off-diagonal `(i, j)`. Diagonal entries are kept as-is.
+    ///
+    /// `General` data is returned unchanged, since it already lists every entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MtxError::NonZeroSkewDiagonal`] if a skew-symmetric matrix has
+    /// a non-zero diagonal entry, and [`MtxError::UnsupportedLayout`] for dense
+    /// data, since expansion is only meaningful for sparse/pattern storage, and
+    /// for skew-symmetric/hermitian pattern data, which has no value to mirror.
+    pub fn expand_symmetry(&self) -> Result<Self, MtxError>
+    where
+        T: Clone,
+    {
+        match self {
+            MtxData::Sparse(dims, indices, values, sym) => {
+                let (indices, values) = expand_coo(
+                    indices,
+                    values,
+                    *sym,
+                    |v| T::zero() - v.clone(),
+                    T::clone,
+                    |v| *v == T::zero(),
+                )?;
+                Ok(MtxData::Sparse(*dims, indices, values, *sym))
+            }
+            MtxData::SparseComplex(dims, indices, values, sym) => {
+                let (indices, values) = expand_coo(
+                    indices,
+                    values,
+                    *sym,
+                    |v: &Complex<T>| {
+                        Complex::new(T::zero() - v.re.clone(), T::zero() - v.im.clone())
+                    },
+                    |v: &Complex<T>| Complex::new(v.re.clone(), T::zero() - v.im.clone()),
+                    |v: &Complex<T>| v.re == T::zero() && v.im == T::zero(),
+                )?;
+                Ok(MtxData::SparseComplex(*dims, indices, values, *sym))
+            }
+            MtxData::SparsePattern(dims, indices, sym) => {
+                let indices = expand_coo_pattern(indices, *sym)?;
+                Ok(MtxData::SparsePattern(*dims, indices, *sym))
+            }
+            MtxData::Dense(..) | MtxData::DenseComplex(..) => Err(MtxError::UnsupportedLayout(
+                "expand_symmetry is only supported for sparse/pattern storage".to_owned(),
+            )),
         }
     }
 }
 
-fn parse_sparse_coo<T: Num, const NDIM: usize>(
-    reader: &mut BufReader<File>,
+/// Mirror every stored off-diagonal `(i, j, v)` of a symmetric/skew-symmetric/
+/// hermitian sparse matrix into `(j, i, mirror(v))`, where `mirror` is `neg` for
+/// skew-symmetric and `conj` for hermitian (identity for plain symmetric).
+fn expand_coo<V: Clone, const NDIM: usize>(
+    indices: &[[usize; NDIM]],
+    values: &[V],
+    sym: SymInfo,
+    neg: impl Fn(&V) -> V,
+    conj: impl Fn(&V) -> V,
+    is_zero: impl Fn(&V) -> bool,
+) -> Result<(Vec<[usize; NDIM]>, Vec<V>), MtxError> {
+    if sym == SymInfo::General {
+        return Ok((indices.to_vec(), values.to_vec()));
+    }
+    let mut out_indices = Vec::with_capacity(indices.len() * 2);
+    let mut out_values = Vec::with_capacity(values.len() * 2);
+    for (coords, val) in indices.iter().zip(values) {
+        out_indices.push(*coords);
+        out_values.push(val.clone());
+        if coords[0] != coords[1] {
+            let mut mirror = *coords;
+            mirror.swap(0, 1);
+            let mirror_val = match sym {
+                SymInfo::Symmetric => val.clone(),
+                SymInfo::SkewSymmetric => neg(val),
+                SymInfo::Hermitian => conj(val),
+                SymInfo::General => unreachable!(),
+            };
+            out_indices.push(mirror);
+            out_values.push(mirror_val);
+        } else if sym == SymInfo::SkewSymmetric && !is_zero(val) {
+            return Err(MtxError::NonZeroSkewDiagonal);
+        }
+    }
+    Ok((out_indices, out_values))
+}
+
+/// Mirror every stored off-diagonal coordinate of a symmetric pattern matrix.
+/// Skew-symmetric and hermitian patterns carry no value to mirror, so they are
+/// rejected.
+fn expand_coo_pattern<const NDIM: usize>(
+    indices: &[[usize; NDIM]],
+    sym: SymInfo,
+) -> Result<Vec<[usize; NDIM]>, MtxError> {
+    match sym {
+        SymInfo::General => Ok(indices.to_vec()),
+        SymInfo::Symmetric => {
+            let mut out = Vec::with_capacity(indices.len() * 2);
+            for coords in indices {
+                out.push(*coords);
+                if coords[0] != coords[1] {
+                    let mut mirror = *coords;
+                    mirror.swap(0, 1);
+                    out.push(mirror);
+                }
+            }
+            Ok(out)
+        }
+        SymInfo::SkewSymmetric | SymInfo::Hermitian => Err(MtxError::UnsupportedLayout(
+            "pattern matrices have no value to mirror for skew-symmetric/hermitian".to_owned(),
+        )),
+    }
+}
+
+/// Parse a `MtxData` directly from a matrix market string, without touching the
+/// filesystem. Mirrors [`MtxData::from_reader`] on an in-memory [`Cursor`].
+impl<T: Num + Clone, const NDIM: usize> FromStr for MtxData<T, NDIM> {
+    type Err = MtxError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MtxData::from_reader(Cursor::new(s.as_bytes()))
+    }
+}
+
+fn parse_sparse_coo<T: Num, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
     buf: &mut String,
     nnz: usize,
 ) -> Result<(Vec<[usize; NDIM]>, Vec<T>), MtxError> {
@@ -162,28 +581,298 @@ fn parse_sparse_coo<T: Num, const NDIM: usize>(
     }
     Ok((indices, values))
 }
-fn parse_dense_vec<T: Num>(
-    reader: &mut BufReader<File>,
+/// Parsed coordinates and complex values for a sparse `complex` matrix.
+type CooComplex<T, const NDIM: usize> = (Vec<[usize; NDIM]>, Vec<Complex<T>>);
+
+fn parse_sparse_coo_complex<T: Num, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
     buf: &mut String,
-    capacity: usize,
-) -> Result<Vec<T>, MtxError> {
-    let mut v: Vec<T> = Vec::with_capacity(capacity);
-    for _ in 0..capacity {
+    nnz: usize,
+) -> Result<CooComplex<T, NDIM>, MtxError> {
+    let mut values: Vec<Complex<T>> = Vec::with_capacity(nnz);
+    let mut indices: Vec<[usize; NDIM]> = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+        let n = reader.read_line(buf)?;
+        if n == 0 {
+            return Err(MtxError::EarlyEOF);
+        }
+        let (coords, val) = parse_coords_complex(buf)?;
+        indices.push(coords);
+        values.push(val);
+        buf.clear();
+    }
+    Ok((indices, values))
+}
+
+fn parse_sparse_coo_pattern<const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    nnz: usize,
+) -> Result<Vec<[usize; NDIM]>, MtxError> {
+    let mut indices: Vec<[usize; NDIM]> = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+        let n = reader.read_line(buf)?;
+        if n == 0 {
+            return Err(MtxError::EarlyEOF);
+        }
+        indices.push(parse_coords_pattern(buf)?);
+        buf.clear();
+    }
+    Ok(indices)
+}
+
+/// Check a freshly-parsed sparse coordinate against the declared dimensions
+/// and the set of coordinates already seen. `line` is the 1-based index of
+/// the data line within the coordinate block, and `raw` is its untrimmed
+/// text, both carried by [`MtxError::InvalidLine`] on failure.
+///
+/// For symmetric/skew-symmetric/hermitian data, only one side of an
+/// off-diagonal pair may be stored, but the matrix market spec doesn't
+/// mandate which triangle, and real-world files (including this crate's own
+/// `small.mtx` fixture) list the upper one. So `(i, j)` and `(j, i)` are
+/// treated as the same coordinate for duplicate detection regardless of
+/// which triangle it falls in, instead of requiring the lower one.
+fn validate_sparse_coords<const NDIM: usize>(
+    coords: [usize; NDIM],
+    dims: [usize; NDIM],
+    sym: SymInfo,
+    seen: &mut HashSet<[usize; NDIM]>,
+    line: usize,
+    raw: &str,
+) -> Result<(), MtxError> {
+    let invalid_line = |reason: String| MtxError::InvalidLine {
+        line,
+        text: raw.trim_end().to_owned(),
+        reason,
+    };
+    if coords.iter().zip(dims.iter()).any(|(c, d)| c >= d) {
+        return Err(invalid_line(format!(
+            "coordinate {coords:?} out of bounds for dims {dims:?}"
+        )));
+    }
+    let dedup_key = if matches!(
+        sym,
+        SymInfo::Symmetric | SymInfo::SkewSymmetric | SymInfo::Hermitian
+    ) && coords[0] < coords[1]
+    {
+        let mut mirrored = coords;
+        mirrored.swap(0, 1);
+        mirrored
+    } else {
+        coords
+    };
+    if !seen.insert(dedup_key) {
+        return Err(invalid_line(format!("duplicate coordinate {coords:?}")));
+    }
+    Ok(())
+}
+
+fn parse_sparse_coo_validated<T: Num, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    nnz: usize,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<(Vec<[usize; NDIM]>, Vec<T>), MtxError> {
+    let mut values: Vec<T> = Vec::with_capacity(nnz);
+    let mut indices: Vec<[usize; NDIM]> = Vec::with_capacity(nnz);
+    let mut seen: HashSet<[usize; NDIM]> = HashSet::with_capacity(nnz);
+    for line_no in 1..=nnz {
         let n = reader.read_line(buf)?;
         if n == 0 {
             return Err(MtxError::EarlyEOF);
         }
-        match T::from_str_radix(buf.trim_end(), 10) {
-            Ok(num) => {
-                v.push(num);
+        let (coords, val) = parse_coords_val(buf)?;
+        validate_sparse_coords(coords, dims, sym, &mut seen, line_no, buf)?;
+        indices.push(coords);
+        values.push(val);
+        buf.clear();
+    }
+    Ok((indices, values))
+}
+
+fn parse_sparse_coo_complex_validated<T: Num, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    nnz: usize,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<CooComplex<T, NDIM>, MtxError> {
+    let mut values: Vec<Complex<T>> = Vec::with_capacity(nnz);
+    let mut indices: Vec<[usize; NDIM]> = Vec::with_capacity(nnz);
+    let mut seen: HashSet<[usize; NDIM]> = HashSet::with_capacity(nnz);
+    for line_no in 1..=nnz {
+        let n = reader.read_line(buf)?;
+        if n == 0 {
+            return Err(MtxError::EarlyEOF);
+        }
+        let (coords, val) = parse_coords_complex(buf)?;
+        validate_sparse_coords(coords, dims, sym, &mut seen, line_no, buf)?;
+        indices.push(coords);
+        values.push(val);
+        buf.clear();
+    }
+    Ok((indices, values))
+}
+
+fn parse_sparse_coo_pattern_validated<const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    nnz: usize,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<Vec<[usize; NDIM]>, MtxError> {
+    let mut indices: Vec<[usize; NDIM]> = Vec::with_capacity(nnz);
+    let mut seen: HashSet<[usize; NDIM]> = HashSet::with_capacity(nnz);
+    for line_no in 1..=nnz {
+        let n = reader.read_line(buf)?;
+        if n == 0 {
+            return Err(MtxError::EarlyEOF);
+        }
+        let coords = parse_coords_pattern(buf)?;
+        validate_sparse_coords(coords, dims, sym, &mut seen, line_no, buf)?;
+        indices.push(coords);
+        buf.clear();
+    }
+    Ok(indices)
+}
+
+/// Read exactly `count` whitespace-separated tokens from `reader`, pulling in as
+/// many physical lines as needed. The matrix market `array` format allows
+/// several values per line and doesn't align data lines with logical rows, so
+/// dense parsing can't assume "one value per `read_line`" like the sparse path
+/// does.
+fn read_tokens<R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    count: usize,
+) -> Result<Vec<String>, MtxError> {
+    let mut tokens = Vec::with_capacity(count);
+    while tokens.len() < count {
+        buf.clear();
+        let n = reader.read_line(buf)?;
+        if n == 0 {
+            return Err(MtxError::EarlyEOF);
+        }
+        tokens.extend(buf.split_whitespace().map(str::to_owned));
+    }
+    tokens.truncate(count);
+    Ok(tokens)
+}
+
+/// Number of values physically stored for a `dims[0] x dims[1]` dense `array`:
+/// the full matrix for `general`, only the lower triangle (column-major,
+/// diagonal included) for the other symmetry kinds.
+fn dense_stored_count<const NDIM: usize>(
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<usize, MtxError> {
+    match sym {
+        SymInfo::General => Ok(dims.iter().product()),
+        SymInfo::Symmetric => {
+            let n = dims[0];
+            Ok(n * (n + 1) / 2)
+        }
+        SymInfo::SkewSymmetric | SymInfo::Hermitian => Err(MtxError::UnsupportedLayout(
+            "skew-symmetric/hermitian dense array storage is not supported".to_owned(),
+        )),
+    }
+}
+
+/// Scatter the column-major lower-triangle (or full) storage of a dense `array`
+/// into a row-major `dims[0] x dims[1]` matrix, mirroring the upper triangle for
+/// `symmetric` storage.
+fn dense_row_major<T: Clone, const NDIM: usize>(
+    stored: Vec<T>,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+    zero: T,
+) -> Vec<T> {
+    let nrows = dims[0];
+    let ncols = dims[1];
+    let mut row_major = vec![zero; nrows * ncols];
+    let mut it = stored.into_iter();
+    match sym {
+        SymInfo::General => {
+            for c in 0..ncols {
+                for r in 0..nrows {
+                    row_major[r * ncols + c] = it.next().expect("stored count matches dims");
+                }
             }
-            Err(_) => {
-                return Err(MtxError::InvalidNum(buf.clone()));
+        }
+        _ => {
+            for c in 0..ncols {
+                for r in c..nrows {
+                    let val = it.next().expect("stored count matches dims");
+                    row_major[r * ncols + c] = val.clone();
+                    row_major[c * ncols + r] = val;
+                }
             }
         }
-        buf.clear();
     }
-    Ok(v)
+    row_major
+}
+
+/// Inverse of [`dense_row_major`]: extract the column-major storage order (the
+/// lower triangle only, for `symmetric` data) from a row-major dense matrix, so
+/// that [`MtxData::write`] emits the same layout [`MtxData::from_reader`] expects.
+fn dense_stored_values<T, const NDIM: usize>(
+    values: &[T],
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Vec<&T> {
+    let nrows = dims[0];
+    let ncols = dims[1];
+    let mut out = Vec::with_capacity(values.len());
+    match sym {
+        SymInfo::Symmetric => {
+            for c in 0..ncols {
+                for r in c..nrows {
+                    out.push(&values[r * ncols + c]);
+                }
+            }
+        }
+        _ => {
+            for c in 0..ncols {
+                for r in 0..nrows {
+                    out.push(&values[r * ncols + c]);
+                }
+            }
+        }
+    }
+    out
+}
+
+fn parse_dense_vec<T: Num + Clone, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<Vec<T>, MtxError> {
+    let stored_count = dense_stored_count(dims, sym)?;
+    let tokens = read_tokens(reader, buf, stored_count)?;
+    let stored = tokens
+        .into_iter()
+        .map(|t| T::from_str_radix(&t, 10).or(Err(MtxError::InvalidNum(t))))
+        .collect::<Result<Vec<T>, MtxError>>()?;
+    Ok(dense_row_major(stored, dims, sym, T::zero()))
+}
+
+fn parse_dense_complex_vec<T: Num + Clone, const NDIM: usize, R: BufRead>(
+    reader: &mut R,
+    buf: &mut String,
+    dims: [usize; NDIM],
+    sym: SymInfo,
+) -> Result<Vec<Complex<T>>, MtxError> {
+    let stored_count = dense_stored_count(dims, sym)?;
+    let tokens = read_tokens(reader, buf, stored_count * 2)?;
+    let mut stored = Vec::with_capacity(stored_count);
+    for pair in tokens.chunks_exact(2) {
+        let re = T::from_str_radix(&pair[0], 10).or(Err(MtxError::InvalidNum(pair[0].clone())))?;
+        let im = T::from_str_radix(&pair[1], 10).or(Err(MtxError::InvalidNum(pair[1].clone())))?;
+        stored.push(Complex::new(re, im));
+    }
+    Ok(dense_row_major(stored, dims, sym, Complex::new(T::zero(), T::zero())))
 }
 fn parse_coords_val<T: Num, const NDIM: usize>(line: &str) -> Result<([usize; NDIM], T), MtxError> {
     let mut value: Option<T> = None;
@@ -194,7 +883,7 @@ fn parse_coords_val<T: Num, const NDIM: usize>(line: &str) -> Result<([usize; ND
             value = Some(num);
         } else {
             let num = usize::from_str(num)?;
-            dims[i] = num - 1; // mtx is 1 based indexing while rust is 0
+            dims[i] = num.checked_sub(1).ok_or(MtxError::ZeroCoordinate)?; // mtx is 1 based indexing while rust is 0
         }
     }
     if let Some(val) = value {
@@ -204,6 +893,39 @@ fn parse_coords_val<T: Num, const NDIM: usize>(line: &str) -> Result<([usize; ND
     }
 }
 
+/// Parse a `complex` data line: `NDIM` coordinates followed by a real and an
+/// imaginary value.
+fn parse_coords_complex<T: Num, const NDIM: usize>(
+    line: &str,
+) -> Result<([usize; NDIM], Complex<T>), MtxError> {
+    let mut tokens = line.split_whitespace();
+    let mut dims = [0; NDIM];
+    for d in dims.iter_mut() {
+        let num = tokens.next().ok_or(MtxError::EarlyLineEnd)?;
+        *d = usize::from_str(num)?
+            .checked_sub(1) // mtx is 1 based indexing while rust is 0
+            .ok_or(MtxError::ZeroCoordinate)?;
+    }
+    let re = tokens.next().ok_or(MtxError::EarlyLineEnd)?;
+    let im = tokens.next().ok_or(MtxError::EarlyLineEnd)?;
+    let re = T::from_str_radix(re, 10).or(Err(MtxError::InvalidNum(re.to_owned())))?;
+    let im = T::from_str_radix(im, 10).or(Err(MtxError::InvalidNum(im.to_owned())))?;
+    Ok((dims, Complex::new(re, im)))
+}
+
+/// Parse a `pattern` data line: `NDIM` coordinates and no value.
+fn parse_coords_pattern<const NDIM: usize>(line: &str) -> Result<[usize; NDIM], MtxError> {
+    let mut dims = [0; NDIM];
+    let mut tokens = line.split_whitespace();
+    for d in dims.iter_mut() {
+        let num = tokens.next().ok_or(MtxError::EarlyLineEnd)?;
+        *d = usize::from_str(num)?
+            .checked_sub(1) // mtx is 1 based indexing while rust is 0
+            .ok_or(MtxError::ZeroCoordinate)?;
+    }
+    Ok(dims)
+}
+
 fn parse_sizes<const NDIM: usize>(
     buf: &mut String,
 ) -> Result<([usize; NDIM], Option<usize>), MtxError> {
@@ -217,7 +939,6 @@ fn parse_sizes<const NDIM: usize>(
             dims[i] = num;
         }
     }
-    println!("buf = {buf}, dims = {dims:?}");
     buf.clear();
     if dims.iter().any(|d| *d == 0) {
         Err(MtxError::EarlySizesHeaderEnd)
@@ -226,10 +947,7 @@ fn parse_sizes<const NDIM: usize>(
     }
 }
 
-fn parse_banner(
-    reader: &mut BufReader<File>,
-    buf: &mut String,
-) -> Result<(bool, SymInfo), MtxError> {
+fn parse_banner<R: BufRead>(reader: &mut R, buf: &mut String) -> Result<TypeCode, MtxError> {
     let n = reader.read_line(buf)?;
     if n == 0 {
         return Err(MtxError::EarlyEOF);
@@ -238,25 +956,32 @@ fn parse_banner(
     // usually a banner look like this
     // %%MatrixMarket matrix coordinate integer symmetric
     // so we skip the 2 first fields and parse the next
-    println!("banner = {buf}");
     let mut banner = buf.split_whitespace().skip(2);
-    let is_sparse = banner
+    let layout = banner.next().ok_or(MtxError::EarlyBannerEnd)?;
+    let sparse = match layout {
+        "coordinate" => true,
+        "array" => false,
+        other => return Err(MtxError::UnsupportedLayout(other.to_owned())),
+    };
+    let num_kind = banner
         .next()
-        .map(|c| c == "coordinate")
-        .ok_or_else(|| MtxError::EarlyBannerEnd)?;
-    // so we skip the type since this already given with generic T
-    let _type = banner.next().ok_or_else(|| MtxError::EarlyBannerEnd);
+        .map(NumKind::from_str)
+        .ok_or(MtxError::EarlyBannerEnd)??;
     let sym = banner
         .next()
         .map(SymInfo::from_str)
-        .ok_or_else(|| MtxError::EarlyBannerEnd)??;
+        .ok_or(MtxError::EarlyBannerEnd)??;
     buf.clear();
 
-    Ok((is_sparse, sym))
+    Ok(TypeCode {
+        sparse,
+        num_kind,
+        sym,
+    })
 }
 
 const COMMENT: char = '%';
-fn skip_comments(reader: &mut BufReader<File>, buf: &mut String) -> Result<(), MtxError> {
+fn skip_comments<R: BufRead>(reader: &mut R, buf: &mut String) -> Result<(), MtxError> {
     let mut comment = true;
     while comment {
         buf.clear();
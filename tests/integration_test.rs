@@ -1,4 +1,6 @@
-use matrix_market_rs::{MtxData, SymInfo};
+use matrix_market_rs::{MtxData, MtxError, SymInfo};
+use num_complex::Complex;
+use std::io::Cursor;
 
 #[test]
 fn test_read_sparse_sym_small() {
@@ -31,11 +33,189 @@ fn test_read_sparse_sym_big() {
     }
 }
 
+#[test]
+fn test_round_trip_sparse_sym() {
+    let original: MtxData<i32> = MtxData::from_file("small.mtx").unwrap();
+    original.to_file("small_roundtrip.mtx").unwrap();
+    let reloaded: MtxData<i32> = MtxData::from_file("small_roundtrip.mtx").unwrap();
+    assert_eq!(original, reloaded);
+}
+
+#[test]
+fn test_parse_sparse_sym_from_str() {
+    let mtx_content = "%%MatrixMarket matrix coordinate integer symmetric\n5 5 7\n1 1 1\n1 3 2\n2 2 3\n2 4 4\n3 5 5\n4 5 6\n5 5 7\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    let expected_dims = [5, 5];
+    let expected_values = vec![1, 2, 3, 4, 5, 6, 7];
+    let expected_indices = vec![[0, 0], [0, 2], [1, 1], [1, 3], [2, 4], [3, 4], [4, 4]];
+    use MtxData::*;
+    match output {
+        Sparse(dims, indices, values, sym) => {
+            assert_eq!(dims, expected_dims, "Dimensions dont match");
+            assert_eq!(values, expected_values, "Values dont match");
+            assert_eq!(indices, expected_indices, "Values dont match");
+            assert!(matches!(sym, SymInfo::Symmetric));
+        }
+        _dense => panic!("Expected Sparse not Dense"),
+    }
+}
+
+#[test]
+fn test_parse_sparse_complex() {
+    let mtx_content =
+        "%%MatrixMarket matrix coordinate complex general\n2 2 2\n1 1 3 1\n2 2 4 -2\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    use MtxData::*;
+    match output {
+        SparseComplex(dims, indices, values, sym) => {
+            assert_eq!(dims, [2, 2], "Dimensions dont match");
+            assert_eq!(indices, vec![[0, 0], [1, 1]], "Indices dont match");
+            assert_eq!(
+                values,
+                vec![Complex::new(3, 1), Complex::new(4, -2)],
+                "Values dont match"
+            );
+            assert!(matches!(sym, SymInfo::General));
+        }
+        _other => panic!("Expected SparseComplex"),
+    }
+}
+
+#[test]
+fn test_parse_sparse_pattern() {
+    let mtx_content = "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 1\n2 2\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    use MtxData::*;
+    match output {
+        SparsePattern(dims, indices, sym) => {
+            assert_eq!(dims, [2, 2], "Dimensions dont match");
+            assert_eq!(indices, vec![[0, 0], [1, 1]], "Indices dont match");
+            assert!(matches!(sym, SymInfo::General));
+        }
+        _other => panic!("Expected SparsePattern"),
+    }
+}
+
+#[test]
+fn test_expand_symmetry_sparse() {
+    let mtx_content = "%%MatrixMarket matrix coordinate integer symmetric\n3 3 2\n1 1 1\n2 1 5\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    let expanded = output.expand_symmetry().unwrap();
+    use MtxData::*;
+    match expanded {
+        Sparse(dims, indices, values, sym) => {
+            assert_eq!(dims, [3, 3]);
+            assert_eq!(indices, vec![[0, 0], [1, 0], [0, 1]]);
+            assert_eq!(values, vec![1, 5, 5]);
+            assert!(matches!(sym, SymInfo::Symmetric));
+        }
+        _other => panic!("Expected Sparse"),
+    }
+}
+
+#[test]
+fn test_expand_symmetry_skew() {
+    let mtx_content =
+        "%%MatrixMarket matrix coordinate integer skew-symmetric\n3 3 1\n2 1 5\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    let expanded = output.expand_symmetry().unwrap();
+    use MtxData::*;
+    match expanded {
+        Sparse(_dims, indices, values, _sym) => {
+            assert_eq!(indices, vec![[1, 0], [0, 1]]);
+            assert_eq!(values, vec![5, -5]);
+        }
+        _other => panic!("Expected Sparse"),
+    }
+}
+
+#[test]
+fn test_validated_rejects_out_of_bounds_coordinate() {
+    let mtx_content = "%%MatrixMarket matrix coordinate integer general\n2 2 1\n3 1 5\n";
+    let err = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap_err();
+    match err {
+        MtxError::InvalidLine { line, .. } => assert_eq!(line, 1),
+        other => panic!("Expected InvalidLine, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_validated_rejects_duplicate_coordinate() {
+    let mtx_content = "%%MatrixMarket matrix coordinate integer general\n2 2 2\n1 1 5\n1 1 6\n";
+    let err = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap_err();
+    match err {
+        MtxError::InvalidLine { line, .. } => assert_eq!(line, 2),
+        other => panic!("Expected InvalidLine, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_validated_accepts_upper_triangle_entry() {
+    // The spec doesn't mandate which triangle is stored, and real files
+    // (e.g. this crate's own small.mtx fixture) list the upper one.
+    let mtx_content = "%%MatrixMarket matrix coordinate integer symmetric\n2 2 1\n1 2 5\n";
+    let output = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap();
+    use MtxData::*;
+    match output {
+        Sparse(dims, indices, values, sym) => {
+            assert_eq!(dims, [2, 2]);
+            assert_eq!(indices, vec![[0, 1]]);
+            assert_eq!(values, vec![5]);
+            assert!(matches!(sym, SymInfo::Symmetric));
+        }
+        _other => panic!("Expected Sparse"),
+    }
+}
+
+#[test]
+fn test_validated_rejects_entry_listed_on_both_triangles() {
+    let mtx_content =
+        "%%MatrixMarket matrix coordinate integer symmetric\n2 2 2\n1 2 5\n2 1 5\n";
+    let err = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap_err();
+    match err {
+        MtxError::InvalidLine { line, .. } => assert_eq!(line, 2),
+        other => panic!("Expected InvalidLine, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_validated_accepts_valid_sparse() {
+    let mtx_content =
+        "%%MatrixMarket matrix coordinate integer symmetric\n2 2 2\n1 1 3\n2 2 4\n";
+    let output = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap();
+    use MtxData::*;
+    match output {
+        Sparse(dims, indices, values, sym) => {
+            assert_eq!(dims, [2, 2]);
+            assert_eq!(indices, vec![[0, 0], [1, 1]]);
+            assert_eq!(values, vec![3, 4]);
+            assert!(matches!(sym, SymInfo::Symmetric));
+        }
+        _other => panic!("Expected Sparse"),
+    }
+}
+
+#[test]
+fn test_validated_rejects_zero_coordinate_without_panicking() {
+    let mtx_content = "%%MatrixMarket matrix coordinate integer general\n2 2 1\n0 1 5\n";
+    let err = MtxData::<i32>::from_reader_validated(Cursor::new(mtx_content.as_bytes()))
+        .unwrap_err();
+    assert!(matches!(err, MtxError::ZeroCoordinate));
+}
+
 #[test]
 fn test_read_dense() {
     let output: MtxData<i32> = MtxData::from_file("small_dense.mtx").unwrap();
     use MtxData::*;
-    let expected_values = vec![1, 2, 3, 4, 5, 6];
+    // small_dense.mtx lists "1 2 3 4 5 6" which is column-major storage of a
+    // 2x3 matrix: column 0 is [1, 2], column 1 is [3, 4], column 2 is [5, 6].
+    // Reordered into row-major that's [1, 3, 5, 2, 4, 6].
+    let expected_values = vec![1, 3, 5, 2, 4, 6];
     match output {
         Dense(dims, values, sym) => {
             assert_eq!(dims, [2, 3], "Dimensions dont match");
@@ -45,3 +225,51 @@ fn test_read_dense() {
         _sparse => panic!("Expected Dense not sparse"),
     }
 }
+
+#[test]
+fn test_read_dense_column_major_multi_value_lines() {
+    // Same logical 2x3 matrix as `small_dense.mtx`, but packed two values per
+    // physical line to exercise tokenizing across line boundaries.
+    let mtx_content = "%%MatrixMarket matrix array integer general\n2 3\n1 2\n3 4\n5 6\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    use MtxData::*;
+    match output {
+        Dense(dims, values, sym) => {
+            assert_eq!(dims, [2, 3]);
+            let at = |r: usize, c: usize| values[r * dims[1] + c];
+            assert_eq!(at(0, 0), 1);
+            assert_eq!(at(1, 0), 2);
+            assert_eq!(at(0, 1), 3);
+            assert_eq!(at(1, 1), 4);
+            assert_eq!(at(0, 2), 5);
+            assert_eq!(at(1, 2), 6);
+            assert!(matches!(sym, SymInfo::General));
+        }
+        _sparse => panic!("Expected Dense not sparse"),
+    }
+}
+
+#[test]
+fn test_read_dense_symmetric_lower_triangle() {
+    let mtx_content =
+        "%%MatrixMarket matrix array integer symmetric\n3 3\n1\n2\n3\n4\n5\n6\n";
+    let output: MtxData<i32> = mtx_content.parse().unwrap();
+    use MtxData::*;
+    match output {
+        Dense(dims, values, sym) => {
+            assert_eq!(dims, [3, 3]);
+            let at = |r: usize, c: usize| values[r * dims[1] + c];
+            assert_eq!(at(0, 0), 1);
+            assert_eq!(at(1, 0), 2);
+            assert_eq!(at(2, 0), 3);
+            assert_eq!(at(0, 1), 2);
+            assert_eq!(at(1, 1), 4);
+            assert_eq!(at(2, 1), 5);
+            assert_eq!(at(0, 2), 3);
+            assert_eq!(at(1, 2), 5);
+            assert_eq!(at(2, 2), 6);
+            assert!(matches!(sym, SymInfo::Symmetric));
+        }
+        _sparse => panic!("Expected Dense not sparse"),
+    }
+}